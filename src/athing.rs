@@ -19,6 +19,11 @@ impl<'a> AThing<'a> {
         self.0.find(Class("rank")).next()?.extract_number_prefix()
     }
 
+    /// The HN item id from the `tr.athing` `id` attribute, e.g. `20820036`.
+    pub fn id(&self) -> Option<u64> {
+        self.0.attr("id")?.parse().ok()
+    }
+
     pub fn line2(&self) -> Option<AThingLine2<'a>> {
         std::iter::successors(self.0.next(), |n| n.next())
             .find(|n| n.name() == Some("tr"))
@@ -44,6 +49,17 @@ impl<'a> AThingLine2<'a> {
     pub fn comments(&self) -> Option<usize> {
         self.0.find(Name("a")).last()?.extract_number_prefix()
     }
+
+    /// Raw relative age text, e.g. `"1 hour ago"`, from the `age` span.
+    pub fn age(&self) -> Option<String> {
+        let text = self.0.find(Class("age")).next()?.text();
+        let text = text.trim();
+        if text.is_empty() {
+            None
+        } else {
+            Some(text.to_owned())
+        }
+    }
 }
 
 trait NodeExt {
@@ -101,6 +117,14 @@ mod athing_test {
         assert_eq!(athing.rank(), Some(22));
     }
 
+    #[test]
+    fn parse_id() {
+        let document = Document::from(ATHING_FRAGMENT);
+        let athing = AThing(document.find(Class("athing")).next().unwrap());
+
+        assert_eq!(athing.id(), Some(20820036));
+    }
+
     #[test]
     fn parse_author() {
         let document = Document::from(ATHING_FRAGMENT);
@@ -124,4 +148,12 @@ mod athing_test {
         let line2 = athing.line2().unwrap();
         assert_eq!(line2.comments(), Some(14));
     }
+
+    #[test]
+    fn parse_age() {
+        let document = Document::from(ATHING_FRAGMENT);
+        let athing = AThing(document.find(Class("athing")).next().unwrap());
+        let line2 = athing.line2().unwrap();
+        assert_eq!(line2.age().as_deref(), Some("1 hour ago"));
+    }
 }
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+/// Sliding window size for orthogonal-sparse-bigram tokenization.
+const OSB_WINDOW: usize = 5;
+
+/// Multinomial Naive Bayes title classifier.
+///
+/// Titles are tokenized into unigrams plus orthogonal-sparse-bigrams (OSB):
+/// for each word, pairs `(w_i, w_{i+k})` for `k` in `1..OSB_WINDOW` are emitted
+/// joined with the gap distance, capturing word co-occurrence without a full
+/// n-gram explosion. Scoring uses Laplace-smoothed log probabilities so the
+/// argmax class is stable across title lengths.
+///
+/// The model serializes to JSON so a trained classifier can be reused across
+/// runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Classifier {
+    /// Known class labels; every count vector is indexed parallel to this.
+    classes: Vec<String>,
+    /// Per-token occurrence counts, one entry per class.
+    token_counts: HashMap<String, Vec<u32>>,
+    /// Total token occurrences per class.
+    class_totals: Vec<u64>,
+    /// Number of trained documents per class.
+    doc_counts: Vec<u64>,
+}
+
+impl Classifier {
+    /// Train on a single labeled title, registering `label` if unseen.
+    pub fn train(&mut self, title: &str, label: &str) {
+        let class = self.class_index(label);
+        self.doc_counts[class] += 1;
+        for token in tokenize(title) {
+            let num_classes = self.classes.len();
+            let counts = self.token_counts.entry(token).or_insert_with(|| vec![0; num_classes]);
+            counts[class] += 1;
+            self.class_totals[class] += 1;
+        }
+    }
+
+    /// Train from a file of `title<TAB>label` lines.
+    pub fn train_file(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let text = std::fs::read_to_string(path)?;
+        for line in text.lines() {
+            if let Some(tab) = line.find('\t') {
+                let (title, label) = line.split_at(tab);
+                self.train(title, label[1..].trim());
+            }
+        }
+        Ok(())
+    }
+
+    /// The most likely class for `title`, or `None` if the model is untrained.
+    pub fn classify(&self, title: &str) -> Option<String> {
+        let tokens = tokenize(title);
+        let vocab_size = self.token_counts.len() as f64;
+        let total_docs: u64 = self.doc_counts.iter().sum();
+        if total_docs == 0 {
+            return None;
+        }
+
+        let mut best: Option<(usize, f64)> = None;
+        for class in 0..self.classes.len() {
+            // log P(class) with Laplace-smoothed prior
+            let prior = (self.doc_counts[class] as f64 + 1.0)
+                / (total_docs as f64 + self.classes.len() as f64);
+            let denom = self.class_totals[class] as f64 + vocab_size;
+
+            let mut score = prior.ln();
+            for token in &tokens {
+                let count = self.token_counts.get(token).map_or(0, |c| c[class]);
+                score += ((count as f64 + 1.0) / denom).ln();
+            }
+
+            if best.map_or(true, |(_, b)| score > b) {
+                best = Some((class, score));
+            }
+        }
+        best.map(|(class, _)| self.classes[class].clone())
+    }
+
+    /// Persist the trained model to `path` as JSON.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(std::fs::File::create(path)?, self)?;
+        Ok(())
+    }
+
+    /// Load a model previously written by [`Classifier::save`].
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_reader(std::fs::File::open(path)?)?)
+    }
+
+    /// Index of `label`, appending a new class (and widening count vectors) if unseen.
+    fn class_index(&mut self, label: &str) -> usize {
+        if let Some(i) = self.classes.iter().position(|c| c == label) {
+            return i;
+        }
+        self.classes.push(label.to_owned());
+        self.class_totals.push(0);
+        self.doc_counts.push(0);
+        for counts in self.token_counts.values_mut() {
+            counts.push(0);
+        }
+        self.classes.len() - 1
+    }
+}
+
+/// Tokenize a title into lowercased unigrams plus OSB co-occurrence pairs.
+fn tokenize(title: &str) -> Vec<String> {
+    let words: Vec<String> = title.to_lowercase().split_whitespace().map(str::to_owned).collect();
+    let mut tokens = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        tokens.push(word.clone());
+        for k in 1..OSB_WINDOW {
+            if let Some(other) = words.get(i + k) {
+                tokens.push(format!("{}|{}|{}", word, k, other));
+            }
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod classifier_test {
+    use super::*;
+
+    fn trained() -> Classifier {
+        let mut c = Classifier::default();
+        c.train("Rust async runtime internals", "interesting");
+        c.train("Deep dive into the Rust borrow checker", "interesting");
+        c.train("Show HN: my new crypto coin", "noise");
+        c.train("Why you should buy my startup course", "noise");
+        c
+    }
+
+    #[test]
+    fn classifies_seen_topics() {
+        let c = trained();
+        assert_eq!(c.classify("Rust borrow checker explained").as_deref(), Some("interesting"));
+        assert_eq!(c.classify("Buy my crypto course").as_deref(), Some("noise"));
+    }
+
+    #[test]
+    fn untrained_returns_none() {
+        assert_eq!(Classifier::default().classify("anything").as_deref(), None);
+    }
+
+    #[test]
+    fn tokenize_emits_osb_pairs() {
+        let tokens = tokenize("a b c");
+        assert!(tokens.contains(&"a".to_owned()));
+        assert!(tokens.contains(&"a|1|b".to_owned()));
+        assert!(tokens.contains(&"a|2|c".to_owned()));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::Post;
+use std::collections::HashMap;
+
+/// In-memory TF-IDF inverted index over scraped posts.
+///
+/// Posts are tokenized into lowercased terms and stored as postings
+/// (`term -> [(doc_id, term_freq)]`). Queries rank documents by the sum over
+/// query terms of `term_freq * idf`, where `idf = ln(N / df)`. This ranks by
+/// relevance to a query rather than by HN rank, over accumulated crawls.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    postings: HashMap<String, Vec<(usize, usize)>>,
+    docs: Vec<Post>,
+}
+
+impl SearchIndex {
+    /// Build an index from posts, indexing `title` and optionally `author`.
+    pub fn build(docs: Vec<Post>, index_author: bool) -> Self {
+        let mut postings: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+
+        for (doc_id, post) in docs.iter().enumerate() {
+            let mut freqs: HashMap<String, usize> = HashMap::new();
+            for term in tokenize(&post.title) {
+                *freqs.entry(term).or_default() += 1;
+            }
+            if index_author {
+                if let Some(author) = &post.author {
+                    for term in tokenize(author) {
+                        *freqs.entry(term).or_default() += 1;
+                    }
+                }
+            }
+            for (term, tf) in freqs {
+                postings.entry(term).or_default().push((doc_id, tf));
+            }
+        }
+
+        SearchIndex { postings, docs }
+    }
+
+    /// Return the top-`k` posts ranked by TF-IDF relevance to `query`.
+    pub fn query(&self, query: &str, k: usize) -> Vec<&Post> {
+        let n = self.docs.len() as f64;
+        let mut scores = vec![0.0_f64; self.docs.len()];
+
+        for term in tokenize(query) {
+            if let Some(postings) = self.postings.get(&term) {
+                let idf = (n / postings.len() as f64).ln();
+                for &(doc_id, tf) in postings {
+                    scores[doc_id] += tf as f64 * idf;
+                }
+            }
+        }
+
+        let mut ranked: Vec<usize> = (0..self.docs.len()).filter(|&i| scores[i] > 0.0).collect();
+        ranked.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).expect("finite score"));
+        ranked.into_iter().take(k).map(|i| &self.docs[i]).collect()
+    }
+}
+
+/// Split text into lowercased alphanumeric terms.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod index_test {
+    use super::*;
+
+    fn posts() -> Vec<Post> {
+        serde_json::from_str(
+            r#"[
+                {"title": "Rust async runtime internals", "uri": "a", "rank": 1},
+                {"title": "Python async web frameworks", "uri": "b", "rank": 2},
+                {"title": "A gentle intro to category theory", "uri": "c", "rank": 3}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn ranks_by_relevance() {
+        let index = SearchIndex::build(posts(), false);
+        let hits = index.query("rust async", 5);
+        assert_eq!(hits[0].title, "Rust async runtime internals");
+        // "async" also matches the python post, so it ranks second
+        assert_eq!(hits[1].title, "Python async web frameworks");
+    }
+
+    #[test]
+    fn respects_k_and_skips_non_matches() {
+        let index = SearchIndex::build(posts(), false);
+        let hits = index.query("async", 1);
+        assert_eq!(hits.len(), 1);
+
+        assert!(index.query("category", 5).iter().all(|p| p.title.contains("category")));
+        assert!(index.query("nonexistentterm", 5).is_empty());
+    }
+}
@@ -1,16 +1,33 @@
 mod athing;
+mod classifier;
+mod index;
+mod output;
+mod section;
+mod session;
+mod templates;
 
 use crate::athing::*;
+use crate::classifier::Classifier;
+use crate::index::SearchIndex;
+use crate::output::Output;
+use crate::section::Section;
+use crate::session::Session;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use select::{
     document::Document,
     predicate::{Class, Name, Predicate},
 };
 use std::error::Error;
+use std::io::Write;
+use std::path::Path;
 
 /// Maximum post fetch count
 const MAX_POSTS: usize = 100;
 
-fn main() {
+/// Minimum `--watch` poll interval in seconds, to stay polite to HN.
+const MIN_WATCH_SECS: u64 = 30;
+
+fn main() -> Result<(), Box<dyn Error>> {
     // handle cli args
     let args = clap::App::new("Hacker News Scraper")
         .version("0.1")
@@ -23,91 +40,324 @@ fn main() {
                 .validator(|pl| pl.parse::<usize>().map(|_| ()).map_err(|err| format!("{}", err)))
                 .help("Number of posts to fetch between 0 & 100, default 30"),
         )
+        .arg(
+            clap::Arg::with_name("section")
+                .long("section")
+                .value_name("SECTION")
+                .validator(|s| s.parse::<Section>().map(|_| ()))
+                .help("HN section: news, newest, ask, show, jobs or front[=YYYY-MM-DD]"),
+        )
+        .arg(
+            clap::Arg::with_name("watch")
+                .long("watch")
+                .value_name("SECONDS")
+                .validator(|s| s.parse::<u64>().map(|_| ()).map_err(|e| format!("{}", e)))
+                .help("Poll every SECONDS (min 30) and stream only newly-appearing posts as NDJSON"),
+        )
+        .arg(
+            clap::Arg::with_name("output")
+                .long("output")
+                .value_name("FORMAT")
+                .validator(|o| o.parse::<Output>().map(|_| ()))
+                .help("Output format: json (default), rss, atom or html"),
+        )
+        .arg(
+            clap::Arg::with_name("model")
+                .long("model")
+                .value_name("FILE")
+                .help("Naive Bayes model JSON file, used with --train/--classify/--only"),
+        )
+        .arg(
+            clap::Arg::with_name("train")
+                .long("train")
+                .value_name("FILE")
+                .requires("model")
+                .help("Train a model from a file of title<TAB>label lines and save to --model"),
+        )
+        .arg(
+            clap::Arg::with_name("classify")
+                .long("classify")
+                .requires("model")
+                .help("Annotate each post with its predicted class from --model"),
+        )
+        .arg(
+            clap::Arg::with_name("only")
+                .long("only")
+                .value_name("CLASS")
+                .requires("model")
+                .help("Only emit posts the model classifies as CLASS"),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("index")
+                .about("Rank previously-dumped JSON posts (read from stdin) by a query")
+                .arg(
+                    clap::Arg::with_name("query")
+                        .value_name("QUERY")
+                        .required(true)
+                        .help("Query terms to rank posts by"),
+                )
+                .arg(
+                    clap::Arg::with_name("top")
+                        .short("k")
+                        .long("top")
+                        .value_name("K")
+                        .validator(|k| k.parse::<usize>().map(|_| ()).map_err(|e| format!("{}", e)))
+                        .help("Number of top results to return, default 10"),
+                )
+                .arg(
+                    clap::Arg::with_name("author")
+                        .long("author")
+                        .help("Also index post authors"),
+                ),
+        )
         .get_matches();
 
+    // search mode: rank dumped JSON posts from stdin by a query
+    if let Some(sub) = args.subcommand_matches("index") {
+        let posts: Vec<Post> = serde_json::from_reader(std::io::stdin())?;
+        let k = sub.value_of("top").and_then(|k| k.parse().ok()).unwrap_or(10);
+        let index = SearchIndex::build(posts, sub.is_present("author"));
+        let hits = index.query(sub.value_of("query").expect("query is required"), k);
+        serde_json::to_writer_pretty(std::io::stdout(), &hits)?;
+        return Ok(());
+    }
+
+    // training mode: build a model from labeled titles, then exit
+    if let Some(train_file) = args.value_of("train") {
+        let model_path = args.value_of("model").expect("--train requires --model");
+        let mut classifier = Classifier::default();
+        classifier.train_file(Path::new(train_file))?;
+        classifier.save(Path::new(model_path))?;
+        return Ok(());
+    }
+
+    let output = match args.value_of("output") {
+        Some(o) => o.parse()?,
+        None => Output::default(),
+    };
+
     let n = args.value_of("posts").and_then(|p| p.parse().ok()).unwrap_or(30).min(MAX_POSTS);
     if n == 0 {
-        println!("[]");
-        return;
+        println!("{}", output.render(&[], Utc::now())?);
+        return Ok(());
+    }
+
+    let section = match args.value_of("section") {
+        Some(s) => s.parse()?,
+        None => Section::default(),
+    };
+
+    // optionally load a classifier, used by --classify/--only & --watch alike
+    let only = args.value_of("only");
+    let classifier = if args.is_present("classify") || only.is_some() {
+        let model_path = args.value_of("model").expect("--classify/--only requires --model");
+        Some(Classifier::load(Path::new(model_path))?)
+    } else {
+        None
+    };
+
+    let session = Session::new();
+
+    if let Some(watch) = args.value_of("watch") {
+        let interval = watch.parse::<u64>().unwrap_or(30).max(MIN_WATCH_SECS);
+        return watch_posts(&session, &section, n, interval, classifier.as_ref(), only);
     }
 
     // fetch the post data
-    let posts = fetch_posts(n);
+    let mut posts = fetch_posts(&session, &section, n)?;
 
-    // write the posts to stdout in json format
-    serde_json::to_writer_pretty(std::io::stdout(), &posts).expect("write to stdout");
+    // optionally classify (annotate) & filter by class
+    if let Some(classifier) = &classifier {
+        for post in &mut posts {
+            post.class = classifier.classify(&post.title);
+        }
+        if let Some(only) = only {
+            posts.retain(|p| p.class.as_deref() == Some(only));
+        }
+    }
+
+    // write the posts to stdout in the requested format
+    println!("{}", output.render(&posts, Utc::now())?);
+    Ok(())
 }
 
 /// Fetch `n` hacker news posts
-fn fetch_posts(n: usize) -> Vec<Post> {
-    let client = reqwest::Client::new();
-
-    (1..)
-        .map(|page_num| {
-            fetch_news_html(&client, page_num).unwrap_or_else(|err| {
-                panic!("Failed to fetch page {} from hacker news: {}", page_num, err);
-            })
-        })
-        .flat_map(|page| {
-            page.find(Name("tr").and(Class("athing")))
-                .filter_map(|tr| Post::try_from(AThing(tr)))
-                .collect::<Vec<_>>()
-        })
-        .take(n)
-        .collect()
+///
+/// Pages are fetched in order until `n` posts are gathered or a page yields
+/// no posts. A transient fetch failure is retried by the [`Session`]; a
+/// persistent failure ends the crawl and is returned as an error rather than
+/// panicking (posts from earlier pages are not returned).
+fn fetch_posts(session: &Session, section: &Section, n: usize) -> Result<Vec<Post>, Box<dyn Error>> {
+    let mut posts = Vec::new();
+    let now = Utc::now();
+
+    for page_num in 1.. {
+        let page = fetch_news_html(session, section, page_num)?;
+        let page_posts = page
+            .find(Name("tr").and(Class("athing")))
+            .filter_map(|tr| Post::try_from(AThing(tr), section, now))
+            .collect::<Vec<_>>();
+
+        if page_posts.is_empty() {
+            break;
+        }
+        posts.extend(page_posts);
+        if posts.len() >= n {
+            break;
+        }
+    }
+
+    posts.truncate(n);
+    Ok(posts)
+}
+
+/// Poll `section` every `interval` seconds, printing newly-appeared posts as NDJSON.
+///
+/// The first poll seeds the set of already-seen posts without printing
+/// anything; only posts that show up in a later poll are considered "new"
+/// and written, one JSON object per line, to stdout. `classifier`/`only`
+/// annotate & filter each poll's posts exactly as the non-watch path does.
+/// Runs until the process is killed or a fetch returns a persistent error.
+fn watch_posts(
+    session: &Session,
+    section: &Section,
+    n: usize,
+    interval: u64,
+    classifier: Option<&Classifier>,
+    only: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let mut seen = std::collections::HashSet::new();
+    let mut first_poll = true;
+
+    loop {
+        let mut posts = fetch_posts(session, section, n)?;
+
+        if let Some(classifier) = classifier {
+            for post in &mut posts {
+                post.class = classifier.classify(&post.title);
+            }
+            if let Some(only) = only {
+                posts.retain(|p| p.class.as_deref() == Some(only));
+            }
+        }
+
+        for post in posts {
+            if seen.insert(dedup_key(&post)) && !first_poll {
+                println!("{}", serde_json::to_string(&post)?);
+            }
+        }
+        std::io::stdout().flush()?;
+        first_poll = false;
+
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+    }
+}
+
+/// Stable identity for a post across polls: its HN item id, falling back to
+/// its uri for the rare post that lacks one.
+fn dedup_key(post: &Post) -> String {
+    match post.id {
+        Some(id) => id.to_string(),
+        None => post.uri.clone(),
+    }
 }
 
 /// Hacker news post data
 ///
 /// Optional fields handle occasional posts that lack data e.g. "(X is Hiring ...)"
-#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 struct Post {
     title: String,
     uri: String,
     rank: usize,
+    /// HN item id, used to dedup posts across `--watch` cycles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     author: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     points: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     comments: Option<usize>,
+    /// Raw relative age text as shown on HN, e.g. "1 hour ago".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    age: Option<String>,
+    /// `age` normalized to an absolute RFC3339 timestamp, relative to fetch time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    /// Predicted class label, set when `--classify`/`--only` is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<String>,
 }
 
 impl Post {
     /// Construct from node `<tr class='athing'...` returns `None` if invalid.
     ///
+    /// `section` selects how tolerant parsing is: sections without a line-2
+    /// row (e.g. jobs) skip author/points/comments rather than treating their
+    /// absence as a parse failure. `now` is the fetch time, used to normalize
+    /// the relative `age` text into an absolute `created_at` timestamp.
+    ///
     /// Truncates `title` & `author` to max 256 characters (business requirement)
-    fn try_from(athing: AThing<'_>) -> Option<Self> {
-        let (uri, mut title) = athing.uri_and_title().expect("uri + title");
-        let line2 = athing.line2()?;
-
+    fn try_from(athing: AThing<'_>, section: &Section, now: DateTime<Utc>) -> Option<Self> {
+        let (uri, mut title) = athing.uri_and_title()?;
         title.truncate(256);
 
+        let line2 = if section.has_line2() { athing.line2() } else { None };
+        let age = line2.and_then(|l| l.age());
+        let created_at = age
+            .as_deref()
+            .and_then(|age| normalize_age(age, now))
+            .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Secs, true));
+
         Some(Post {
             title,
             uri,
             rank: athing.rank()?,
-            author: line2.author().map(|mut author| {
+            id: athing.id(),
+            author: line2.and_then(|l| l.author()).map(|mut author| {
                 author.truncate(256);
                 author
             }),
-            points: line2.points(),
-            comments: line2.comments(),
+            points: line2.and_then(|l| l.points()),
+            comments: line2.and_then(|l| l.comments()),
+            age,
+            created_at,
+            class: None,
         })
     }
 }
 
+/// Normalize a relative HN age string (e.g. "2 hours ago") to an absolute time.
+///
+/// Calendar units are approximated (month = 30 days, year = 365 days) since HN
+/// only reports coarse relative ages. Returns `None` for unrecognized text.
+fn normalize_age(age: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let mut parts = age.split_whitespace();
+    let amount: i64 = parts.next()?.parse().ok()?;
+    let duration = match parts.next()?.trim_end_matches('s') {
+        "minute" => Duration::minutes(amount),
+        "hour" => Duration::hours(amount),
+        "day" => Duration::days(amount),
+        "month" => Duration::days(amount * 30),
+        "year" => Duration::days(amount * 365),
+        _ => return None,
+    };
+    Some(now - duration)
+}
+
 /// Blocking html fetch from hacker news website
 #[cfg(not(feature = "mock-news"))]
-fn fetch_news_html(client: &reqwest::Client, page: usize) -> Result<Document, Box<dyn Error>> {
-    const HACKER_NEWS_URL: &str = "https://news.ycombinator.com/news";
-    let response = client.get(HACKER_NEWS_URL).query(&[("p", page)]).send()?.error_for_status()?;
+fn fetch_news_html(session: &Session, section: &Section, page: usize) -> Result<Document, Box<dyn Error>> {
+    const HACKER_NEWS_BASE: &str = "https://news.ycombinator.com/";
+    let url = format!("{}{}", HACKER_NEWS_BASE, section.path());
+    let response = session.send(|client| client.get(&url).query(&[("p", page)]))?;
     Ok(Document::from_read(response)?)
 }
 
 /// Mock html fetch implemented for the first 3 pages on 28-August-2019
 #[cfg(feature = "mock-news")]
-fn fetch_news_html(_: &reqwest::Client, page: usize) -> Result<Document, Box<dyn Error>> {
+fn fetch_news_html(_: &Session, _: &Section, page: usize) -> Result<Document, Box<dyn Error>> {
     let html = match page {
         1 => include_str!("../tests/news-p1.html"),
         2 => include_str!("../tests/news-p2.html"),
@@ -125,78 +375,139 @@ mod test {
 
     #[test]
     fn fetch_top_3() {
-        let posts = fetch_posts(3);
+        let posts = fetch_posts(&Session::new(), &Section::News, 3).unwrap();
         assert_eq!(posts.len(), 3);
 
-        let mut posts = posts.into_iter();
-        assert_eq!(
-            posts.next(),
-            Some(Post {
-                title: "Words that do Handstands".into(),
-                uri: "http://hardmath123.github.io/ambigrams.html".into(),
-                rank: 1,
-                author: Some("hardmath123".into()),
-                points: Some(82),
-                comments: Some(14),
-            })
-        );
-        assert_eq!(posts.next(), Some(Post {
-            title: "Possible detection of a black hole with a mass that was thought to be impossible".into(),
-            uri: "https://www.quantamagazine.org/possible-detection-of-a-black-hole-so-big-it-should-not-exist-20190828/".into(),
-            rank: 2,
-            author: Some("theafh".into()),
-            points: Some(58),
-            comments: Some(35),
-        }));
+        // `created_at` is relative to wall-clock fetch time, so assert the
+        // stable fields; age normalization is covered by `age_test`.
+        let p = &posts[0];
+        assert_eq!(p.title, "Words that do Handstands");
+        assert_eq!(p.uri, "http://hardmath123.github.io/ambigrams.html");
+        assert_eq!(p.rank, 1);
+        assert_eq!(p.author.as_deref(), Some("hardmath123"));
+        assert_eq!(p.points, Some(82));
+        assert_eq!(p.comments, Some(14));
+
+        let p = &posts[1];
         assert_eq!(
-            posts.next(),
-            Some(Post {
-                title: "Lessons from Stripe".into(),
-                uri: "https://markmcgranaghan.com/lessons-from-stripe".into(),
-                rank: 3,
-                author: Some("rspivak".into()),
-                points: Some(110),
-                comments: Some(30),
-            })
+            p.title,
+            "Possible detection of a black hole with a mass that was thought to be impossible"
         );
+        assert_eq!(p.uri, "https://www.quantamagazine.org/possible-detection-of-a-black-hole-so-big-it-should-not-exist-20190828/");
+        assert_eq!(p.rank, 2);
+        assert_eq!(p.author.as_deref(), Some("theafh"));
+        assert_eq!(p.points, Some(58));
+        assert_eq!(p.comments, Some(35));
+
+        let p = &posts[2];
+        assert_eq!(p.title, "Lessons from Stripe");
+        assert_eq!(p.uri, "https://markmcgranaghan.com/lessons-from-stripe");
+        assert_eq!(p.rank, 3);
+        assert_eq!(p.author.as_deref(), Some("rspivak"));
+        assert_eq!(p.points, Some(110));
+        assert_eq!(p.comments, Some(30));
     }
 
     /// Hiring posts lack author/points/comments data
     #[test]
     fn fetch_hiring_post_12() {
-        let posts = fetch_posts(12);
+        let posts = fetch_posts(&Session::new(), &Section::News, 12).unwrap();
         assert_eq!(posts.len(), 12);
 
+        let p = &posts[11];
         assert_eq!(
-            posts[11],
-            Post {
-                title: "Mimir (YC S15) Is Hiring a Product Designer to Help Us Improve CS Education".into(),
-                uri: "https://hire.withgoogle.com/public/jobs/mimirhqcom/view/P_AAAAAADAACHKrbvKW9X25u".into(),
-                rank: 12,
-                author: None,
-                points: None,
-                comments: None,
-            }
+            p.title,
+            "Mimir (YC S15) Is Hiring a Product Designer to Help Us Improve CS Education"
         );
+        assert_eq!(
+            p.uri,
+            "https://hire.withgoogle.com/public/jobs/mimirhqcom/view/P_AAAAAADAACHKrbvKW9X25u"
+        );
+        assert_eq!(p.rank, 12);
+        assert_eq!(p.author, None);
+        assert_eq!(p.points, None);
+        assert_eq!(p.comments, None);
     }
 
     #[test]
     fn fetch_more_than_1_page() {
-        let posts = fetch_posts(74);
+        let posts = fetch_posts(&Session::new(), &Section::News, 74).unwrap();
 
         let ranks: Vec<_> = posts.iter().map(|p| p.rank).collect();
         assert_eq!(ranks, (1..=74).collect::<Vec<_>>());
 
+        let p = &posts[72];
+        assert_eq!(p.title, "Anthony Levandowski Charged with Theft of Trade Secrets");
         assert_eq!(
-            posts[72],
-            Post {
-                title: "Anthony Levandowski Charged with Theft of Trade Secrets".into(),
-                uri: "https://www.nytimes.com/2019/08/27/technology/google-trade-secrets-levandowski.html".into(),
-                rank: 73,
-                author: Some("coloneltcb".into()),
-                points: Some(440),
-                comments: Some(339),
-            }
+            p.uri,
+            "https://www.nytimes.com/2019/08/27/technology/google-trade-secrets-levandowski.html"
         );
+        assert_eq!(p.rank, 73);
+        assert_eq!(p.author.as_deref(), Some("coloneltcb"));
+        assert_eq!(p.points, Some(440));
+        assert_eq!(p.comments, Some(339));
+    }
+}
+
+#[cfg(test)]
+mod age_test {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.ymd(2019, 8, 28).and_hms(12, 0, 0)
+    }
+
+    #[test]
+    fn normalize_hours() {
+        let at = normalize_age("2 hours ago", now()).unwrap();
+        assert_eq!(at.to_rfc3339_opts(SecondsFormat::Secs, true), "2019-08-28T10:00:00Z");
+    }
+
+    #[test]
+    fn normalize_singular_and_days() {
+        assert_eq!(
+            normalize_age("1 minute ago", now()).unwrap().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "2019-08-28T11:59:00Z"
+        );
+        assert_eq!(
+            normalize_age("3 days ago", now()).unwrap().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "2019-08-25T12:00:00Z"
+        );
+    }
+
+    #[test]
+    fn normalize_unknown() {
+        assert!(normalize_age("just now", now()).is_none());
+    }
+}
+
+#[cfg(test)]
+mod watch_test {
+    use super::*;
+
+    fn post(id: Option<u64>, uri: &str) -> Post {
+        Post {
+            title: "t".into(),
+            uri: uri.into(),
+            rank: 1,
+            id,
+            author: None,
+            points: None,
+            comments: None,
+            age: None,
+            created_at: None,
+            class: None,
+        }
+    }
+
+    #[test]
+    fn dedup_key_prefers_id() {
+        assert_eq!(dedup_key(&post(Some(42), "http://x/a")), "42");
+    }
+
+    #[test]
+    fn dedup_key_falls_back_to_uri() {
+        assert_eq!(dedup_key(&post(None, "http://x/a")), "http://x/a");
     }
 }
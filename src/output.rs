@@ -0,0 +1,131 @@
+use crate::templates;
+use crate::Post;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+/// Rendered output format for a post listing.
+///
+/// `Json` preserves the scraped structure verbatim; `Rss`/`Atom` emit a
+/// syndication feed and `Html` a standalone listing page, rendered through
+/// the pluggable [`crate::templates`] engine, mapping `title` -> item title,
+/// `uri` -> link, `author`/`points`/`comments` into the description and
+/// `created_at` into the entry timestamp.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Output {
+    /// Pretty-printed JSON (the default).
+    Json,
+    /// RSS 2.0 feed.
+    Rss,
+    /// Atom 1.0 feed.
+    Atom,
+    /// Standalone HTML listing page.
+    Html,
+}
+
+impl Output {
+    /// Render `posts` into this format. `now` is the fetch time, used by
+    /// `Rss`/`Atom` as the entry/feed timestamp fallback for posts whose age
+    /// didn't normalize into a `created_at` (see [`crate::templates`]).
+    ///
+    /// Only JSON can fail, when serialization does; the template-rendered
+    /// formats always succeed, since the built-in templates are known-valid.
+    pub fn render(&self, posts: &[Post], now: DateTime<Utc>) -> Result<String, serde_json::Error> {
+        match self {
+            Output::Json => serde_json::to_string_pretty(posts),
+            Output::Rss => Ok(templates::render(templates::RSS, posts, now)),
+            Output::Atom => Ok(templates::render(templates::ATOM, posts, now)),
+            Output::Html => Ok(templates::render(templates::HTML, posts, now)),
+        }
+    }
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Output::Json
+    }
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Output::Json),
+            "rss" => Ok(Output::Rss),
+            "atom" => Ok(Output::Atom),
+            "html" => Ok(Output::Html),
+            other => Err(format!("unknown output `{}`", other)),
+        }
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Output::Json => "json",
+            Output::Rss => "rss",
+            Output::Atom => "atom",
+            Output::Html => "html",
+        })
+    }
+}
+
+#[cfg(test)]
+mod output_test {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.ymd(2019, 8, 28).and_hms(12, 0, 0)
+    }
+
+    fn posts() -> Vec<Post> {
+        serde_json::from_str(
+            r#"[
+                {"title": "Rust & <you>", "uri": "http://x/a", "rank": 1,
+                 "author": "foo", "points": 82, "comments": 14,
+                 "created_at": "2019-08-28T10:00:00Z"},
+                {"title": "Bare job", "uri": "http://x/b", "rank": 2}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("rss".parse(), Ok(Output::Rss));
+        assert_eq!("html".parse(), Ok(Output::Html));
+        assert!("yaml".parse::<Output>().is_err());
+    }
+
+    #[test]
+    fn rss_escapes_and_maps_pubdate() {
+        let rss = Output::Rss.render(&posts(), now()).unwrap();
+        assert!(rss.contains("<title>Rust &amp; &lt;you&gt;</title>"));
+        assert!(rss.contains("<link>http://x/a</link>"));
+        assert!(rss.contains("82 points, by foo, 14 comments"));
+        assert!(rss.contains("<pubDate>Wed, 28 Aug 2019 10:00:00 +0000</pubDate>"));
+        // the bare job post has no line-2 data and no timestamp
+        assert_eq!(rss.matches("<pubDate>").count(), 1);
+    }
+
+    #[test]
+    fn atom_feed_and_entries_are_always_dated() {
+        let atom = Output::Atom.render(&posts(), now()).unwrap();
+        assert_eq!(atom.matches("<entry>").count(), 2);
+        assert!(atom.contains("<author><name>foo</name></author>"));
+        // feed-level id/updated are mandatory per RFC 4287
+        assert!(atom.contains("<id>https://news.ycombinator.com/</id>"));
+        assert_eq!(atom.matches("<updated>").count(), 3); // feed + 2 entries
+        // the bare job post lacks created_at, so its entry falls back to the fetch time
+        assert!(atom.contains("<updated>2019-08-28T12:00:00Z</updated>"));
+    }
+
+    #[test]
+    fn html_lists_links() {
+        let html = Output::Html.render(&posts(), now()).unwrap();
+        assert!(html.contains("<a href=\"http://x/a\">Rust &amp; &lt;you&gt;</a>"));
+        assert!(html.contains("<li><a href=\"http://x/b\">Bare job</a></li>"));
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A hacker news listing page.
+///
+/// Each variant maps to a path under `https://news.ycombinator.com/`. The
+/// common `news`/`newest`/`show` layouts carry points & comments; `ask` &
+/// `jobs` rows omit some of these, which [`crate::Post`] degrades gracefully.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Section {
+    /// Front page (ranked).
+    News,
+    /// Newest submissions.
+    Newest,
+    /// "Ask HN" posts.
+    Ask,
+    /// "Show HN" posts.
+    Show,
+    /// Job postings (no author/points/comments).
+    Jobs,
+    /// A dated front page `front?day=YYYY-MM-DD`, or today's when `None`.
+    Front(Option<String>),
+}
+
+impl Section {
+    /// Relative url of the section, including any query string.
+    pub fn path(&self) -> String {
+        match self {
+            Section::News => "news".into(),
+            Section::Newest => "newest".into(),
+            Section::Ask => "ask".into(),
+            Section::Show => "show".into(),
+            Section::Jobs => "jobs".into(),
+            Section::Front(Some(day)) => format!("front?day={}", day),
+            Section::Front(None) => "front".into(),
+        }
+    }
+
+    /// Whether rows in this section carry author/points/comments data.
+    ///
+    /// Job postings list a bare title & link, so line-2 fields are skipped.
+    pub fn has_line2(&self) -> bool {
+        !matches!(self, Section::Jobs)
+    }
+}
+
+impl Default for Section {
+    fn default() -> Self {
+        Section::News
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path())
+    }
+}
+
+impl FromStr for Section {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // only `front` carries a date, e.g. `front=2019-08-28`; reject the
+        // `=...` suffix on every other section rather than silently dropping it.
+        let (name, day) = match s.find('=') {
+            Some(i) => (&s[..i], Some(s[i + 1..].to_owned())),
+            None => (s, None),
+        };
+        match (name, day) {
+            ("news", None) => Ok(Section::News),
+            ("newest", None) => Ok(Section::Newest),
+            ("ask", None) => Ok(Section::Ask),
+            ("show", None) => Ok(Section::Show),
+            ("jobs", None) => Ok(Section::Jobs),
+            ("front", day) => Ok(Section::Front(day)),
+            (name, Some(_)) => Err(format!("section `{}` does not take a `=...` suffix", name)),
+            (other, None) => Err(format!("unknown section `{}`", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod section_test {
+    use super::*;
+
+    #[test]
+    fn parse_simple() {
+        assert_eq!("newest".parse(), Ok(Section::Newest));
+        assert_eq!("jobs".parse(), Ok(Section::Jobs));
+    }
+
+    #[test]
+    fn parse_dated_front() {
+        assert_eq!("front=2019-08-28".parse(), Ok(Section::Front(Some("2019-08-28".into()))));
+        assert_eq!("front".parse(), Ok(Section::Front(None)));
+    }
+
+    #[test]
+    fn path_includes_day() {
+        assert_eq!(Section::Front(Some("2019-08-28".into())).path(), "front?day=2019-08-28");
+        assert_eq!(Section::Newest.path(), "newest");
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert!("nope".parse::<Section>().is_err());
+    }
+
+    #[test]
+    fn parse_rejects_day_suffix_on_non_front() {
+        assert!("news=2019-08-28".parse::<Section>().is_err());
+        assert!("jobs=2019-08-28".parse::<Section>().is_err());
+    }
+}
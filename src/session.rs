@@ -0,0 +1,129 @@
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+
+/// Default `User-Agent` sent with every request.
+const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Default number of retries for transient failures.
+const DEFAULT_RETRIES: u32 = 4;
+
+/// Default initial backoff, doubled after each failed attempt.
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Reusable http session.
+///
+/// Wraps a single `reqwest::Client` with a persistent cookie jar, a fixed
+/// `User-Agent` & a retry policy with exponential backoff for `429`/`5xx`
+/// responses. Sharing one session across page fetches keeps connections &
+/// cookies alive and makes multi-page crawls robust against rate limiting.
+#[derive(Debug)]
+pub struct Session {
+    client: Client,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Session {
+    /// A session with default user-agent, retry & backoff settings.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Begin configuring a session.
+    pub fn builder() -> SessionBuilder {
+        SessionBuilder::default()
+    }
+
+    /// Send a request built by `f`, retrying transient failures.
+    ///
+    /// Transient failures (connection errors, `429` & `5xx` responses) are
+    /// retried up to the configured count, sleeping for an exponentially
+    /// growing backoff between attempts. Other errors are returned immediately.
+    pub fn send<F>(&self, f: F) -> reqwest::Result<Response>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match f(&self.client).send().and_then(Response::error_for_status) {
+                Ok(response) => return Ok(response),
+                Err(err) => {
+                    if attempt >= self.retries || !is_transient(&err) {
+                        return Err(err);
+                    }
+                    std::thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for [`Session`].
+#[derive(Debug)]
+pub struct SessionBuilder {
+    user_agent: String,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Default for SessionBuilder {
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.into(),
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+        }
+    }
+}
+
+impl SessionBuilder {
+    /// Override the `User-Agent` header.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Maximum number of retries for transient failures.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Initial backoff, doubled after each failed attempt.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Build the configured [`Session`].
+    pub fn build(self) -> Session {
+        let client = Client::builder()
+            .cookie_store(true)
+            .user_agent(self.user_agent)
+            .build()
+            .expect("build reqwest client");
+
+        Session { client, retries: self.retries, backoff: self.backoff }
+    }
+}
+
+/// Whether an error is worth retrying: a transport error or a `429`/`5xx` status.
+fn is_transient(err: &reqwest::Error) -> bool {
+    match err.status() {
+        Some(status) => {
+            status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        }
+        // no status => transport-level error (connect/timeout), worth a retry
+        None => true,
+    }
+}
@@ -0,0 +1,187 @@
+//! Pluggable feed/HTML rendering.
+//!
+//! Mirrors Rocket's `dyn_templates`: the concrete template engine is chosen
+//! at compile time via a Cargo feature, so only one template crate ends up
+//! in the binary. Enable exactly one of `tera` (default) or `handlebars`.
+//! Built-in templates live under `templates/` and are embedded with
+//! `include_str!`, so the scraper stays a single binary with no runtime
+//! template lookup.
+
+use crate::Post;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+/// Registered template names, one per [`crate::output::Output`] feed format.
+pub const RSS: &str = "rss.xml";
+pub const ATOM: &str = "atom.xml";
+pub const HTML: &str = "listing.html";
+
+const FEED_LINK: &str = "https://news.ycombinator.com/";
+
+/// Per-post fields a template needs, precomputed so templates stay plain
+/// interpolation rather than carrying date/text formatting logic.
+#[derive(Serialize)]
+struct Item {
+    title: String,
+    link: String,
+    description: String,
+    author: Option<String>,
+    /// RFC 2822, only `Some` when `created_at` parsed (RSS `pubDate`).
+    pub_date: Option<String>,
+    /// RFC 3339, always present — falls back to `now` (Atom entry `updated`).
+    updated: String,
+}
+
+impl Item {
+    fn new(post: &Post, now: DateTime<Utc>) -> Self {
+        Item {
+            title: post.title.clone(),
+            link: post.uri.clone(),
+            description: describe(post),
+            author: post.author.clone(),
+            pub_date: post.created_at.as_deref().and_then(rfc2822),
+            updated: post.created_at.clone().unwrap_or_else(|| now_rfc3339(now)),
+        }
+    }
+}
+
+/// Template context: feed-level fields plus one [`Item`] per post.
+#[derive(Serialize)]
+struct Context {
+    feed_link: &'static str,
+    /// RFC 3339, the latest `Item::updated` or `now` if `items` is empty.
+    feed_updated: String,
+    items: Vec<Item>,
+}
+
+impl Context {
+    fn new(posts: &[Post], now: DateTime<Utc>) -> Self {
+        let items: Vec<Item> = posts.iter().map(|post| Item::new(post, now)).collect();
+        // RFC 3339 UTC timestamps of equal precision sort lexicographically,
+        // so the max string is the most recent timestamp.
+        let feed_updated = items.iter().map(|i| i.updated.clone()).max().unwrap_or_else(|| now_rfc3339(now));
+        Context { feed_link: FEED_LINK, feed_updated, items }
+    }
+}
+
+fn now_rfc3339(now: DateTime<Utc>) -> String {
+    now.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Human-readable summary of a post's line-2 data, e.g. "82 points, by foo, 14 comments".
+fn describe(post: &Post) -> String {
+    let mut parts = Vec::new();
+    if let Some(points) = post.points {
+        parts.push(format!("{} points", points));
+    }
+    if let Some(author) = &post.author {
+        parts.push(format!("by {}", author));
+    }
+    if let Some(comments) = post.comments {
+        parts.push(format!("{} comments", comments));
+    }
+    parts.join(", ")
+}
+
+/// Parse an RFC3339 timestamp into an RFC2822 `pubDate`, or `None` if invalid.
+fn rfc2822(created_at: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(created_at).ok().map(|dt| dt.to_rfc2822())
+}
+
+/// Escape text for inclusion in XML/HTML character data & attribute values.
+///
+/// Used explicitly by the `tera` engine (its built-in autoescaper also
+/// escapes `/`, which would mangle links); `handlebars`'s default escaping
+/// already covers these characters (plus `=` and `` ` ``) and needs no extra
+/// filter.
+#[cfg(feature = "tera")]
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render `template` (one of [`RSS`], [`ATOM`], [`HTML`]) for `posts`.
+///
+/// `now` is the fetch time, used as the timestamp fallback for posts & feeds
+/// without a `created_at` (see [`Item::updated`]/[`Context::feed_updated`]).
+pub fn render(template: &str, posts: &[Post], now: DateTime<Utc>) -> String {
+    engine::render(template, &Context::new(posts, now))
+}
+
+#[cfg(feature = "tera")]
+mod engine {
+    use super::Context;
+    use std::sync::OnceLock;
+    use tera::Tera;
+
+    fn tera() -> &'static Tera {
+        static TERA: OnceLock<Tera> = OnceLock::new();
+        TERA.get_or_init(|| {
+            let mut tera = Tera::default();
+            // Tera's built-in HTML autoescaper also escapes `/`, which would
+            // mangle every link; escaping is instead done explicitly in the
+            // templates via the `escape_xml` filter below.
+            tera.autoescape_on(vec![]);
+            tera.register_filter("escape_xml", escape_xml_filter);
+            tera.add_raw_templates(vec![
+                (super::RSS, include_str!("../templates/rss.xml.tera")),
+                (super::ATOM, include_str!("../templates/atom.xml.tera")),
+                (super::HTML, include_str!("../templates/listing.html.tera")),
+            ])
+            .expect("built-in templates are valid Tera syntax");
+            tera
+        })
+    }
+
+    /// Tera filter escaping XML/HTML character data & attribute values.
+    fn escape_xml_filter(
+        value: &tera::Value,
+        _: &std::collections::HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        let text = tera::try_get_value!("escape_xml", "value", String, value);
+        Ok(tera::Value::String(super::escape_xml(&text)))
+    }
+
+    pub fn render(template: &str, ctx: &Context) -> String {
+        let ctx = tera::Context::from_serialize(ctx).expect("Context always serializes");
+        tera().render(template, &ctx).expect("built-in templates always render")
+    }
+}
+
+#[cfg(feature = "handlebars")]
+mod engine {
+    use super::Context;
+    use handlebars::Handlebars;
+    use std::sync::OnceLock;
+
+    fn handlebars() -> &'static Handlebars<'static> {
+        static HBS: OnceLock<Handlebars<'static>> = OnceLock::new();
+        HBS.get_or_init(|| {
+            let mut hbs = Handlebars::new();
+            hbs.register_template_string(super::RSS, include_str!("../templates/rss.xml.hbs"))
+                .expect("built-in templates are valid Handlebars syntax");
+            hbs.register_template_string(super::ATOM, include_str!("../templates/atom.xml.hbs"))
+                .expect("built-in templates are valid Handlebars syntax");
+            hbs.register_template_string(super::HTML, include_str!("../templates/listing.html.hbs"))
+                .expect("built-in templates are valid Handlebars syntax");
+            hbs
+        })
+    }
+
+    pub fn render(template: &str, ctx: &Context) -> String {
+        handlebars().render(template, ctx).expect("built-in templates always render")
+    }
+}
+
+#[cfg(not(any(feature = "tera", feature = "handlebars")))]
+compile_error!("enable the `tera` or `handlebars` feature to render rss/atom/html output");